@@ -1,50 +1,454 @@
-use clap::Parser;
-use image::GrayImage;
+use clap::{Parser, ValueEnum};
+use image::RgbImage;
 use krnl::macros::module;
-use std::{io::Write, time::Instant};
+use std::{fmt, io::Write, str::FromStr, time::Instant};
 
-fn naive(h: u32, w: u32, max_iterations: u32) -> Vec<u8> {
+/// Color palette used to map escape counts to pixels.
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum Palette {
+    Grayscale,
+    Dark,
+    Fire,
+    Ultra,
+}
+
+impl Palette {
+    /// Passed to the kernel as a specialization constant so the GPU path stays branch-cheap.
+    fn as_u32(self) -> u32 {
+        match self {
+            Self::Grayscale => 0,
+            Self::Dark => 1,
+            Self::Fire => 2,
+            Self::Ultra => 3,
+        }
+    }
+}
+
+/// The window into the complex plane to render, as `real_start,imag_start,real_end,imag_end`.
+#[derive(Clone, Copy, Debug)]
+struct Region {
+    real_start: f32,
+    imag_start: f32,
+    real_end: f32,
+    imag_end: f32,
+}
+
+impl Default for Region {
+    fn default() -> Self {
+        // The classic full view of the set.
+        Self {
+            real_start: -2.5,
+            imag_start: -1.0,
+            real_end: 1.0,
+            imag_end: 1.0,
+        }
+    }
+}
+
+impl FromStr for Region {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fields = s.splitn(4, ',');
+        let mut next = || {
+            fields
+                .next()
+                .ok_or_else(|| "expected 4 comma separated values".to_string())?
+                .trim()
+                .parse::<f32>()
+                .map_err(|e| e.to_string())
+        };
+        let real_start = next()?;
+        let imag_start = next()?;
+        let real_end = next()?;
+        let imag_end = next()?;
+        Ok(Self {
+            real_start,
+            imag_start,
+            real_end,
+            imag_end,
+        })
+    }
+}
+
+impl fmt::Display for Region {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{},{},{},{}",
+            self.real_start, self.imag_start, self.real_end, self.imag_end
+        )
+    }
+}
+
+/// Downsample filter used by `--ssaa`.
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum SsaaFilter {
+    Box,
+    Gaussian,
+}
+
+/// Per-tap weights for an `N`-wide downsample box, normalized so they sum to 1.
+fn ssaa_weights(n: u32, filter: SsaaFilter) -> Vec<f32> {
+    let n = n as usize;
+    let weights: Vec<f32> = match filter {
+        SsaaFilter::Box => vec![1.0; n],
+        SsaaFilter::Gaussian => {
+            // Binomial coefficients of row `n - 1`, the discrete analogue of the
+            // separable `[1, 2, 1]`-style Gaussian kernel for arbitrary `n`.
+            let mut weights = Vec::with_capacity(n);
+            let mut coeff = 1f64;
+            for k in 0..n {
+                weights.push(coeff as f32);
+                coeff *= (n - 1 - k) as f64 / (k + 1) as f64;
+            }
+            weights
+        }
+    };
+    let sum: f32 = weights.iter().sum();
+    weights.into_iter().map(|w| w / sum).collect()
+}
+
+/// Downsamples an `src_w`-wide buffer of `ssaa x ssaa` source blocks down to `dst_w x dst_h`.
+fn downsample(
+    pixels: &[[u8; 3]],
+    src_w: u32,
+    ssaa: u32,
+    filter: SsaaFilter,
+    dst_w: u32,
+    dst_h: u32,
+) -> Vec<[u8; 3]> {
+    use rayon::prelude::*;
+
+    let weights = ssaa_weights(ssaa, filter);
+    (0..dst_h)
+        .into_par_iter()
+        .flat_map_iter(move |dr| {
+            let weights = weights.clone();
+            (0..dst_w).map(move |dc| {
+                let mut acc = [0f32; 3];
+                for (ky, wy) in weights.iter().enumerate() {
+                    let sr = dr * ssaa + ky as u32;
+                    for (kx, wx) in weights.iter().enumerate() {
+                        let sc = dc * ssaa + kx as u32;
+                        let p = pixels[sr as usize * src_w as usize + sc as usize];
+                        let weight = wy * wx;
+                        acc[0] += p[0] as f32 * weight;
+                        acc[1] += p[1] as f32 * weight;
+                        acc[2] += p[2] as f32 * weight;
+                    }
+                }
+                [
+                    acc[0].round() as u8,
+                    acc[1].round() as u8,
+                    acc[2].round() as u8,
+                ]
+            })
+        })
+        .collect()
+}
+
+fn naive(
+    h: u32,
+    w: u32,
+    max_iterations: u32,
+    region: Region,
+    palette: Palette,
+    smooth: bool,
+) -> Vec<[u8; 3]> {
+    let palette = palette.as_u32();
     (0..h)
-        .flat_map(move |r| (0..w).map(move |c| kernels::mandelbro_impl(r, c, h, w, max_iterations)))
+        .flat_map(move |r| {
+            (0..w).map(move |c| {
+                let escape = kernels::mandelbro_impl(
+                    r,
+                    c,
+                    h,
+                    w,
+                    max_iterations,
+                    region.real_start,
+                    region.imag_start,
+                    region.real_end,
+                    region.imag_end,
+                    smooth,
+                );
+                kernels::iteration_to_color(escape, max_iterations, palette)
+            })
+        })
         .collect()
 }
 
-fn parallel(h: u32, w: u32, max_iterations: u32) -> Vec<u8> {
+fn parallel(
+    h: u32,
+    w: u32,
+    max_iterations: u32,
+    region: Region,
+    palette: Palette,
+    smooth: bool,
+) -> Vec<[u8; 3]> {
     use rayon::prelude::*;
 
+    let palette = palette.as_u32();
     (0..h)
         .into_par_iter()
         .flat_map_iter(move |r| {
-            (0..w).map(move |c| kernels::mandelbro_impl(r, c, h, w, max_iterations))
+            (0..w).map(move |c| {
+                let escape = kernels::mandelbro_impl(
+                    r,
+                    c,
+                    h,
+                    w,
+                    max_iterations,
+                    region.real_start,
+                    region.imag_start,
+                    region.real_end,
+                    region.imag_end,
+                    smooth,
+                );
+                kernels::iteration_to_color(escape, max_iterations, palette)
+            })
         })
         .collect()
 }
 
-fn gpu(index: usize, h: u32, w: u32, max_iterations: u32) -> Vec<u8> {
+/// Launch geometry for a dispatch loop: the number of items to hand to each dispatch, in order.
+struct LaunchConfig {
+    dispatch_sizes: Vec<usize>,
+}
+
+/// Picks a launch configuration for `total` work items given the device's `max_groups`,
+/// `default_threads` and `subgroup_threads` (or user overrides for either).
+///
+/// `total` is split into subgroup-sized chunks (the last chunk is shorter than the rest when
+/// `total` isn't a multiple of `subgroup_threads` — unavoidable) and those chunks are split, in
+/// order, into contiguous runs across however many dispatches the target dispatch size implies,
+/// so every dispatch gets an equal share of chunks (off by at most one) instead of one oversized
+/// dispatch and a tiny, sub-subgroup tail. The short last chunk always ends the last dispatch, so
+/// when each dispatch gets a single chunk the last dispatch is that short chunk alone.
+fn launch_config(
+    total: usize,
+    max_groups: usize,
+    default_threads: usize,
+    subgroup_threads: usize,
+    threads_override: Option<usize>,
+    groups_override: Option<usize>,
+) -> LaunchConfig {
+    let threads_per_group = threads_override.unwrap_or(default_threads).max(1);
+    let groups = groups_override.unwrap_or(max_groups).max(1);
+    let mut target_size = (groups * threads_per_group).min(total).max(1);
+    if subgroup_threads > 1 && target_size >= subgroup_threads {
+        target_size -= target_size % subgroup_threads;
+    }
+    let num_dispatches = total.div_ceil(target_size.max(1)).max(1);
+
+    let chunk = subgroup_threads.max(1);
+    let num_chunks = total.div_ceil(chunk);
+    let base_chunks = num_chunks / num_dispatches;
+    let extra_chunks = num_chunks % num_dispatches;
+
+    let mut dispatch_sizes = Vec::with_capacity(num_dispatches);
+    let mut remaining = total;
+    for i in 0..num_dispatches {
+        if remaining == 0 {
+            break;
+        }
+        let chunks = base_chunks + usize::from(i < extra_chunks);
+        let size = (chunks * chunk).min(remaining);
+        dispatch_sizes.push(size);
+        remaining -= size;
+    }
+    LaunchConfig { dispatch_sizes }
+}
+
+fn gpu(
+    index: usize,
+    h: u32,
+    w: u32,
+    max_iterations: u32,
+    region: Region,
+    palette: Palette,
+    smooth: bool,
+    threads_override: Option<usize>,
+    groups_override: Option<usize>,
+) -> Vec<[u8; 3]> {
     use krnl::{buffer::Buffer, device::Device};
 
     let device = Device::builder().index(index).build().unwrap();
     let kernel = kernels::mandelbrot::builder()
         .unwrap()
-        .specialize(h, w, max_iterations)
+        .specialize(h, w, max_iterations, palette.as_u32(), smooth as u32)
         .build(device.clone())
         .unwrap();
-    let mut y = Buffer::zeros(device.clone(), (h * w) as usize).unwrap();
+    let mut y = Buffer::<[u8; 3]>::zeros(device.clone(), h as usize * w as usize).unwrap();
     let device_info = device.info().unwrap();
-    // On some devices max_groups is too small, so split operation into multiple dispatches.
-    let global_threads =
-        (device_info.max_groups() as usize * device_info.default_threads() as usize).min(y.len());
-    for offset in (0..y.len()).step_by(global_threads) {
-        let end = (offset + global_threads).min(y.len());
+    let config = launch_config(
+        y.len(),
+        device_info.max_groups() as usize,
+        device_info.default_threads() as usize,
+        device_info.subgroup_threads() as usize,
+        threads_override,
+        groups_override,
+    );
+    let min_size = config.dispatch_sizes.iter().copied().min().unwrap_or(0);
+    let max_size = config.dispatch_sizes.iter().copied().max().unwrap_or(0);
+    println!(
+        "launch geometry: {} dispatches of {min_size}-{max_size} threads each ({} items total)",
+        config.dispatch_sizes.len(),
+        y.len()
+    );
+    let mut offset = 0;
+    for size in config.dispatch_sizes {
+        let end = offset + size;
         if let Some(y) = y.slice_mut(offset..end) {
-            kernel.dispatch(y, offset as u32).unwrap();
+            kernel
+                .dispatch(
+                    y,
+                    offset as u32,
+                    region.real_start,
+                    region.imag_start,
+                    region.real_end,
+                    region.imag_end,
+                )
+                .unwrap();
         } else {
             break;
         }
+        offset = end;
     }
     y.to_vec().unwrap()
 }
 
+fn naive_bitpack(h: u32, w: u32, max_iterations: u32, region: Region) -> Vec<u8> {
+    let bytes_per_row = w.div_ceil(8);
+    (0..h)
+        .flat_map(move |r| {
+            (0..bytes_per_row).map(move |byte_col| {
+                kernels::mandelbro_bitpack_byte(
+                    r,
+                    byte_col,
+                    h,
+                    w,
+                    max_iterations,
+                    region.real_start,
+                    region.imag_start,
+                    region.real_end,
+                    region.imag_end,
+                )
+            })
+        })
+        .collect()
+}
+
+fn parallel_bitpack(h: u32, w: u32, max_iterations: u32, region: Region) -> Vec<u8> {
+    use rayon::prelude::*;
+
+    let bytes_per_row = w.div_ceil(8);
+    (0..h)
+        .into_par_iter()
+        .flat_map_iter(move |r| {
+            (0..bytes_per_row).map(move |byte_col| {
+                kernels::mandelbro_bitpack_byte(
+                    r,
+                    byte_col,
+                    h,
+                    w,
+                    max_iterations,
+                    region.real_start,
+                    region.imag_start,
+                    region.real_end,
+                    region.imag_end,
+                )
+            })
+        })
+        .collect()
+}
+
+fn gpu_bitpack(
+    index: usize,
+    h: u32,
+    w: u32,
+    max_iterations: u32,
+    region: Region,
+    threads_override: Option<usize>,
+    groups_override: Option<usize>,
+) -> Vec<u8> {
+    use krnl::{buffer::Buffer, device::Device};
+
+    let bytes_per_row = w.div_ceil(8);
+    let device = Device::builder().index(index).build().unwrap();
+    let kernel = kernels::mandelbrot_bitpack::builder()
+        .unwrap()
+        .specialize(h, w, max_iterations, bytes_per_row)
+        .build(device.clone())
+        .unwrap();
+    let mut y = Buffer::<u8>::zeros(device.clone(), (h * bytes_per_row) as usize).unwrap();
+    let device_info = device.info().unwrap();
+    let config = launch_config(
+        y.len(),
+        device_info.max_groups() as usize,
+        device_info.default_threads() as usize,
+        device_info.subgroup_threads() as usize,
+        threads_override,
+        groups_override,
+    );
+    let min_size = config.dispatch_sizes.iter().copied().min().unwrap_or(0);
+    let max_size = config.dispatch_sizes.iter().copied().max().unwrap_or(0);
+    println!(
+        "launch geometry: {} dispatches of {min_size}-{max_size} threads each ({} items total)",
+        config.dispatch_sizes.len(),
+        y.len()
+    );
+    let mut offset = 0;
+    for size in config.dispatch_sizes {
+        let end = offset + size;
+        if let Some(y) = y.slice_mut(offset..end) {
+            kernel
+                .dispatch(
+                    y,
+                    offset as u32,
+                    region.real_start,
+                    region.imag_start,
+                    region.real_end,
+                    region.imag_end,
+                )
+                .unwrap();
+        } else {
+            break;
+        }
+        offset = end;
+    }
+    y.to_vec().unwrap()
+}
+
+/// Writes a 1-bpp PBM (P4) file so the packed bits round-trip without a lossy re-encode.
+fn write_pbm(fname: &str, w: u32, h: u32, bits: &[u8]) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(fname)?;
+    write!(file, "P4\n{w} {h}\n")?;
+    file.write_all(bits)
+}
+
+fn runalgo_bitpack(
+    name: &str,
+    h: u32,
+    w: u32,
+    max_iterations: u32,
+    region: Region,
+    save_image: bool,
+    algo: impl Fn(u32, u32, u32, Region) -> Vec<u8>,
+) {
+    print!("Executing {}... ", name);
+    std::io::stdout().flush().unwrap();
+    let now = Instant::now();
+    let bits = algo(h, w, max_iterations, region);
+    let elapsed = now.elapsed();
+    if save_image {
+        let fname = format!("mandelbrot_{name}.pbm");
+        write_pbm(&fname, w, h, &bits).unwrap();
+        println!("Saved image to {fname:?}.");
+    }
+    println!("{elapsed:.1?}");
+}
+
 #[module]
 mod kernels {
 
@@ -52,19 +456,74 @@ mod kernels {
     use krnl::krnl_core;
     use krnl_core::macros::kernel;
 
-    pub(crate) fn iterations_to_grayscale(i: u32, max_iterations: u32) -> u8 {
+    // Control colors for each palette, evenly spaced across the [0, 1) escape range.
+    const GRAYSCALE_STOPS: [[f32; 3]; 2] = [[0.0, 0.0, 0.0], [255.0, 255.0, 255.0]];
+    const DARK_STOPS: [[f32; 3]; 3] = [[0.0, 0.0, 0.0], [0.0, 32.0, 64.0], [64.0, 224.0, 255.0]];
+    const FIRE_STOPS: [[f32; 3]; 4] = [
+        [0.0, 0.0, 0.0],
+        [128.0, 0.0, 0.0],
+        [255.0, 128.0, 0.0],
+        [255.0, 255.0, 200.0],
+    ];
+    const ULTRA_STOPS: [[f32; 3]; 5] = [
+        [0.0, 7.0, 100.0],
+        [32.0, 107.0, 203.0],
+        [237.0, 255.0, 255.0],
+        [255.0, 170.0, 0.0],
+        [0.0, 2.0, 0.0],
+    ];
+
+    fn lerp_stops(t: f32, stops: &[[f32; 3]]) -> [u8; 3] {
         #[cfg(target_arch = "spirv")]
         use krnl_core::num_traits::Float;
 
-        if i == max_iterations {
-            return 0;
+        let last = stops.len() - 1;
+        let scaled = t * last as f32;
+        let i0 = (scaled as usize).min(last - 1);
+        let frac = scaled - i0 as f32;
+        let mut color = [0u8; 3];
+        let mut k = 0;
+        while k < 3 {
+            let a = stops[i0][k];
+            let b = stops[i0 + 1][k];
+            color[k] = (a + (b - a) * frac).round() as u8;
+            k += 1;
+        }
+        color
+    }
+
+    pub(crate) fn iteration_to_color(escape: f32, max_iterations: u32, palette: u32) -> [u8; 3] {
+        if escape >= max_iterations as f32 {
+            return [0, 0, 0];
+        }
+        let t = escape / max_iterations as f32;
+        match palette {
+            1 => lerp_stops(t, &DARK_STOPS),
+            2 => lerp_stops(t, &FIRE_STOPS),
+            3 => lerp_stops(t, &ULTRA_STOPS),
+            _ => lerp_stops(t, &GRAYSCALE_STOPS),
         }
-        (i as f32 * 255f32 / max_iterations as f32).round() as u8
     }
 
-    pub(crate) fn mandelbro_impl(r: u32, c: u32, h: u32, w: u32, max_iterations: u32) -> u8 {
-        let x0 = ((c as f32) / (w as f32)) * 3.5 - 2.5;
-        let y0 = ((r as f32) / (h as f32)) * 2.0 - 1.0;
+    /// Returns the (possibly fractional, when `smooth`) escape value at `(r, c)`.
+    /// Interior points that never escape return exactly `max_iterations as f32`.
+    pub(crate) fn mandelbro_impl(
+        r: u32,
+        c: u32,
+        h: u32,
+        w: u32,
+        max_iterations: u32,
+        real_start: f32,
+        imag_start: f32,
+        real_end: f32,
+        imag_end: f32,
+        smooth: bool,
+    ) -> f32 {
+        #[cfg(target_arch = "spirv")]
+        use krnl_core::num_traits::Float;
+
+        let x0 = real_start + ((c as f32) / (w as f32)) * (real_end - real_start);
+        let y0 = imag_start + ((r as f32) / (h as f32)) * (imag_end - imag_start);
         let mut x = 0f32;
         let mut y = 0f32;
         let mut iteration = 0;
@@ -74,18 +533,115 @@ mod kernels {
             x = xtemp;
             iteration += 1;
         }
-        iterations_to_grayscale(iteration, max_iterations)
+        if iteration == max_iterations {
+            return max_iterations as f32;
+        }
+        if !smooth {
+            return iteration as f32;
+        }
+        // Run two extra iterations so the magnitude grows well past the escape radius,
+        // which keeps the fractional part of `mu` stable.
+        for _ in 0..2 {
+            let xtemp = x * x - y * y + x0;
+            y = 2.0 * x * y + y0;
+            x = xtemp;
+        }
+        iteration as f32 + 1.0 - ((x * x + y * y).sqrt().ln()).ln() / 2f32.ln()
     }
 
     #[kernel]
-    pub(crate) fn mandelbrot<const H: u32, const W: u32, const I: u32>(
-        #[item] y: &mut u8,
+    pub(crate) fn mandelbrot<
+        const H: u32,
+        const W: u32,
+        const I: u32,
+        const PALETTE: u32,
+        const SMOOTH: u32,
+    >(
+        #[item] y: &mut [u8; 3],
         offset: u32,
+        real_start: f32,
+        imag_start: f32,
+        real_end: f32,
+        imag_end: f32,
     ) {
         let idx = offset + kernel.item_id() as u32;
         let r = idx / W;
         let c = idx % W;
-        *y = mandelbro_impl(r, c, H, W, I);
+        let escape = mandelbro_impl(
+            r,
+            c,
+            H,
+            W,
+            I,
+            real_start,
+            imag_start,
+            real_end,
+            imag_end,
+            SMOOTH != 0,
+        );
+        *y = iteration_to_color(escape, I, PALETTE);
+    }
+
+    /// Classifies the 8 horizontally-adjacent pixels starting at `byte_col * 8` on row `r` as
+    /// in-set (1) or escaped (0), and packs them MSB-first (leftmost pixel = MSB) into one byte.
+    pub(crate) fn mandelbro_bitpack_byte(
+        r: u32,
+        byte_col: u32,
+        h: u32,
+        w: u32,
+        max_iterations: u32,
+        real_start: f32,
+        imag_start: f32,
+        real_end: f32,
+        imag_end: f32,
+    ) -> u8 {
+        let mut byte = 0u8;
+        let mut bit = 0;
+        while bit < 8 {
+            let c = byte_col * 8 + bit;
+            let in_set = if c < w {
+                let escape = mandelbro_impl(
+                    r,
+                    c,
+                    h,
+                    w,
+                    max_iterations,
+                    real_start,
+                    imag_start,
+                    real_end,
+                    imag_end,
+                    false,
+                );
+                (escape >= max_iterations as f32) as u8
+            } else {
+                0
+            };
+            byte = (byte << 1) | in_set;
+            bit += 1;
+        }
+        byte
+    }
+
+    #[kernel]
+    pub(crate) fn mandelbrot_bitpack<
+        const H: u32,
+        const W: u32,
+        const I: u32,
+        const BYTES_PER_ROW: u32,
+    >(
+        #[item] y: &mut u8,
+        offset: u32,
+        real_start: f32,
+        imag_start: f32,
+        real_end: f32,
+        imag_end: f32,
+    ) {
+        let idx = offset + kernel.item_id() as u32;
+        let r = idx / BYTES_PER_ROW;
+        let byte_col = idx % BYTES_PER_ROW;
+        *y = mandelbro_bitpack_byte(
+            r, byte_col, H, W, I, real_start, imag_start, real_end, imag_end,
+        );
     }
 }
 
@@ -94,13 +650,27 @@ fn runalgo(
     h: u32,
     w: u32,
     max_iterations: u32,
+    region: Region,
+    palette: Palette,
+    smooth: bool,
+    ssaa: u32,
+    ssaa_filter: SsaaFilter,
     save_image: bool,
-    algo: impl Fn(u32, u32, u32) -> Vec<u8>,
+    algo: impl Fn(u32, u32, u32, Region, Palette, bool) -> Vec<[u8; 3]>,
 ) {
     print!("Executing {}... ", name);
     std::io::stdout().flush().unwrap();
     let now = Instant::now();
-    let img = GrayImage::from_vec(w, h, algo(h, w, max_iterations)).unwrap();
+    let src_h = h * ssaa;
+    let src_w = w * ssaa;
+    let pixels = algo(src_h, src_w, max_iterations, region, palette, smooth);
+    let pixels = if ssaa > 1 {
+        downsample(&pixels, src_w, ssaa, ssaa_filter, w, h)
+    } else {
+        pixels
+    };
+    let bytes: Vec<u8> = pixels.into_iter().flatten().collect();
+    let img = RgbImage::from_vec(w, h, bytes).unwrap();
     let elapsed = now.elapsed();
     if save_image {
         let fname = format!("mandelbrot_{name}.png");
@@ -128,19 +698,123 @@ struct Cli {
     width: u32,
     #[arg(long, default_value_t = 1000)]
     max_iterations: u32,
+    /// Window into the complex plane, as `real_start,imag_start,real_end,imag_end`.
+    #[arg(long, default_value_t = Region::default())]
+    region: Region,
+    /// Color palette used to map escape counts to pixels.
+    #[arg(long, value_enum, default_value_t = Palette::Grayscale)]
+    palette: Palette,
+    /// Use continuous (fractional) escape values to eliminate iteration banding.
+    #[arg(long)]
+    smooth: bool,
+    /// Override the GPU threads-per-group used to size dispatches (benchmarking).
+    #[arg(long)]
+    threads: Option<usize>,
+    /// Override the GPU group count used to size dispatches (benchmarking).
+    #[arg(long)]
+    groups: Option<usize>,
+    /// Render at N× resolution per axis and downsample, smoothing escape-boundary edges.
+    #[arg(long, default_value_t = 1)]
+    ssaa: u32,
+    /// Downsample filter used when `--ssaa` > 1.
+    #[arg(long, value_enum, default_value_t = SsaaFilter::Box)]
+    ssaa_filter: SsaaFilter,
+    /// Render 1 bit per pixel (in-set/escaped only) and save as a PBM, for max throughput.
+    #[arg(long)]
+    bitpack: bool,
     #[arg(long)]
     save: bool,
 }
 
 fn main() {
     let cli = Cli::parse();
+    if cli.bitpack && cli.ssaa > 1 {
+        eprintln!("--bitpack renders 1 bit per pixel and cannot be supersampled; drop --ssaa or --bitpack.");
+        std::process::exit(1);
+    }
+    if cli.bitpack && !matches!(cli.palette, Palette::Grayscale) {
+        eprintln!(
+            "--bitpack renders 1 bit per pixel and has no palette; drop --palette or --bitpack."
+        );
+        std::process::exit(1);
+    }
+    if cli.bitpack && cli.smooth {
+        eprintln!("--bitpack renders 1 bit per pixel and has no smooth coloring; drop --smooth or --bitpack.");
+        std::process::exit(1);
+    }
     let all = !(cli.naive || cli.parallel || cli.gpu.is_some());
     let height = cli.height;
     let width = cli.width;
     let max_iterations = cli.max_iterations;
+    let region = cli.region;
+    let palette = cli.palette;
+    let smooth = cli.smooth;
+    let threads_override = cli.threads;
+    let groups_override = cli.groups;
+    let ssaa = cli.ssaa.max(1);
+    let ssaa_filter = cli.ssaa_filter;
     let save_image = cli.save;
+    if cli.bitpack {
+        if cli.naive || all {
+            runalgo_bitpack(
+                "naive",
+                height,
+                width,
+                max_iterations,
+                region,
+                save_image,
+                naive_bitpack,
+            );
+        }
+        if cli.parallel || all {
+            runalgo_bitpack(
+                "parallel",
+                height,
+                width,
+                max_iterations,
+                region,
+                save_image,
+                parallel_bitpack,
+            );
+        }
+        if cli.gpu.is_some() || all {
+            let index = cli.gpu.unwrap_or(0);
+            runalgo_bitpack(
+                "gpu",
+                height,
+                width,
+                max_iterations,
+                region,
+                save_image,
+                |h, w, max_iterations, region| {
+                    gpu_bitpack(
+                        index,
+                        h,
+                        w,
+                        max_iterations,
+                        region,
+                        threads_override,
+                        groups_override,
+                    )
+                },
+            );
+        }
+        return;
+    }
     if cli.naive || all {
-        runalgo("naive", height, width, max_iterations, save_image, naive);
+        runalgo(
+            "naive",
+            height,
+            width,
+            max_iterations,
+            region,
+            palette,
+            smooth,
+            ssaa,
+            ssaa_filter,
+            save_image,
+            naive,
+        );
     }
     if cli.parallel || all {
         runalgo(
@@ -148,6 +822,11 @@ fn main() {
             height,
             width,
             max_iterations,
+            region,
+            palette,
+            smooth,
+            ssaa,
+            ssaa_filter,
             save_image,
             parallel,
         );
@@ -159,8 +838,25 @@ fn main() {
             height,
             width,
             max_iterations,
+            region,
+            palette,
+            smooth,
+            ssaa,
+            ssaa_filter,
             save_image,
-            |h, w, max_iterations| gpu(index, h, w, max_iterations),
+            |h, w, max_iterations, region, palette, smooth| {
+                gpu(
+                    index,
+                    h,
+                    w,
+                    max_iterations,
+                    region,
+                    palette,
+                    smooth,
+                    threads_override,
+                    groups_override,
+                )
+            },
         );
     }
 }